@@ -1,11 +1,15 @@
 use crate::detector::ExploitDetector;
 use crate::models::*;
+use crate::oracle::OracleChecker;
 use crate::simulator::StateSimulator;
+use crate::storage::Storage;
 use anyhow::{anyhow, Result};
 use chrono::Utc;
 use log::{debug, info, warn};
 use lru::LruCache;
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::message::VersionedMessage;
 use solana_sdk::signature::Signature;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
@@ -16,21 +20,56 @@ pub struct ChainAnalyzer {
     rpc_client: Arc<RpcClient>,
     detector: ExploitDetector,
     simulator: StateSimulator,
+    oracle_checker: OracleChecker,
     cache: Arc<Mutex<LruCache<String, AnalysisResponse>>>,
+    storage: Option<Arc<Storage>>,
 }
 
 impl ChainAnalyzer {
     pub fn new(rpc_url: &str) -> Self {
         info!("Initializing ChainAnalyzer with RPC: {}", rpc_url);
-        
+
         Self {
             rpc_client: Arc::new(RpcClient::new(rpc_url.to_string())),
             detector: ExploitDetector::new(),
             simulator: StateSimulator::new(),
+            oracle_checker: OracleChecker::new(),
             cache: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1000).unwrap()))),
+            storage: None,
         }
     }
 
+    /// Attaches a persistence backend so analyses/audits are written to Postgres in
+    /// addition to the in-memory cache. Optional: without it, the service behaves as
+    /// a stateless scanner exactly as before.
+    pub fn with_storage(mut self, storage: Option<Arc<Storage>>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    fn extract_program_ids(&self, transaction: &solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta) -> Vec<String> {
+        let Some(tx) = transaction.transaction.transaction.decode() else {
+            return Vec::new();
+        };
+
+        let account_keys = match &tx.message {
+            VersionedMessage::Legacy(m) => m.account_keys.clone(),
+            VersionedMessage::V0(m) => m.account_keys.clone(),
+        };
+
+        // Dedup: a transaction invoking the same program across several instructions
+        // (common — e.g. two Token transfers) would otherwise insert one
+        // `analysis_programs` row per instruction instead of per distinct program.
+        let mut seen = std::collections::HashSet::new();
+        tx.message
+            .instructions()
+            .iter()
+            .filter_map(|ix| account_keys.get(ix.program_id_index as usize))
+            .map(|key| key.to_string())
+            .filter(|program_id| seen.insert(program_id.clone()))
+            .collect()
+    }
+
     pub async fn analyze_transaction(&self, request: AnalysisRequest) -> Result<AnalysisResponse> {
         let start_time = Instant::now();
         
@@ -49,21 +88,40 @@ impl ChainAnalyzer {
         let signature = Signature::from_str(&request.signature)
             .map_err(|e| anyhow!("Invalid signature: {}", e))?;
 
-        // Fetch transaction from chain
+        // Fetch transaction from chain, allowing v0 messages so Address Lookup
+        // Table accounts come back in `meta.loaded_addresses` instead of being
+        // rejected or silently dropped.
         let transaction = self
             .rpc_client
-            .get_transaction(&signature, solana_transaction_status::UiTransactionEncoding::Json)
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(solana_transaction_status::UiTransactionEncoding::Json),
+                    commitment: None,
+                    max_supported_transaction_version: Some(0),
+                },
+            )
             .map_err(|e| anyhow!("Failed to fetch transaction: {}", e))?;
 
         // Simulate transaction execution
         let simulation_result = self.simulator.simulate_transaction(&transaction)?;
 
         // Detect exploits
-        let exploits = self.detector.detect_exploits(&transaction, &simulation_result)?;
+        let mut exploits = self.detector.detect_exploits(&transaction, &simulation_result)?;
+
+        // Cross-reference price-feed accounts (Pyth/Switchboard) for oracle manipulation
+        if let Ok(Some(oracle_exploit)) =
+            self.oracle_checker
+                .check(&self.rpc_client, &transaction, &simulation_result)
+        {
+            exploits.push(oracle_exploit);
+        }
 
         // Analyze state changes
         let state_changes = self.analyze_state_changes(&transaction)?;
 
+        let programs_accessed = self.extract_program_ids(&transaction);
+
         // Calculate risk score
         let risk_score = self.calculate_risk_score(&exploits, &state_changes);
 
@@ -88,6 +146,15 @@ impl ChainAnalyzer {
             cache.put(request.signature.clone(), response.clone());
         }
 
+        if let Some(storage) = &self.storage {
+            if let Err(e) = storage
+                .record_analysis(&request.signature, &programs_accessed, &response)
+                .await
+            {
+                warn!("Failed to persist analysis for {}: {}", request.signature, e);
+            }
+        }
+
         info!(
             "Analysis complete for {} - Risk Score: {:.2}, Duration: {}ms",
             request.signature, risk_score, analysis_duration
@@ -96,6 +163,59 @@ impl ChainAnalyzer {
         Ok(response)
     }
 
+    /// Risk-scores a transaction *before* it lands on chain, using `simulateTransaction`
+    /// instead of the historical replay path `analyze_transaction` uses. This is the
+    /// entry point for front-running/sandwich detection ahead of submission.
+    pub async fn simulate_transaction(
+        &self,
+        request: SimulateTransactionRequest,
+    ) -> Result<AnalysisResponse> {
+        let start_time = Instant::now();
+
+        let transaction = self.simulator.decode_transaction(&request.transaction)?;
+
+        info!("Simulating pending transaction");
+
+        let (simulation_result, synthetic_transaction) = self
+            .simulator
+            .simulate_pending_transaction(&self.rpc_client, &transaction)?;
+
+        // Run the same detectors and oracle cross-reference analyze_transaction uses,
+        // against the synthesized pre-landing transaction, so a pending transaction
+        // actually gets risk-scored instead of always coming back empty.
+        let mut exploits = self
+            .detector
+            .detect_exploits(&synthetic_transaction, &simulation_result)?;
+
+        if let Ok(Some(oracle_exploit)) = self.oracle_checker.check(
+            &self.rpc_client,
+            &synthetic_transaction,
+            &simulation_result,
+        ) {
+            exploits.push(oracle_exploit);
+        }
+
+        // State changes (pre/post balance diffs) aren't available pre-landing, since
+        // simulateTransaction doesn't report them without a real prior account state.
+        let state_changes = Vec::new();
+
+        let risk_score = self.calculate_risk_score(&exploits, &state_changes);
+        let analysis_duration = start_time.elapsed().as_millis() as u64;
+
+        Ok(AnalysisResponse {
+            risk_score,
+            exploits,
+            state_changes,
+            simulation_result,
+            metadata: AnalysisMetadata {
+                timestamp: Utc::now().timestamp(),
+                analysis_duration_ms: analysis_duration,
+                analyzer_version: env!("CARGO_PKG_VERSION").to_string(),
+                network: request.network.unwrap_or_else(|| "mainnet-beta".to_string()),
+            },
+        })
+    }
+
     pub async fn audit_contract(&self, request: ContractAuditRequest) -> Result<ContractAuditResponse> {
         let start_time = Instant::now();
         
@@ -115,7 +235,7 @@ impl ChainAnalyzer {
         }
 
         // Analyze program bytecode
-        let vulnerabilities = self.detector.analyze_program_bytecode(&account.data)?;
+        let mut vulnerabilities = self.detector.analyze_program_bytecode(&account.data)?;
 
         // Get program transactions for behavioral analysis
         let signatures = self
@@ -124,13 +244,54 @@ impl ChainAnalyzer {
             .unwrap_or_default();
 
         let mut instruction_count = 0;
+        let mut write_locks: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        let mut read_locks: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        let mut txs_considered = 0usize;
+
         for sig_info in signatures.iter().take(100) {
             if let Ok(sig) = Signature::from_str(&sig_info.signature) {
-                if let Ok(tx) = self.rpc_client.get_transaction(
+                // Same as `analyze_transaction`: allow v0 messages so ALT accounts come
+                // back in `meta.loaded_addresses` instead of the RPC call erroring and the
+                // transaction being silently dropped from the contention sample.
+                if let Ok(tx) = self.rpc_client.get_transaction_with_config(
                     &sig,
-                    solana_transaction_status::UiTransactionEncoding::Json,
+                    RpcTransactionConfig {
+                        encoding: Some(solana_transaction_status::UiTransactionEncoding::Json),
+                        commitment: None,
+                        max_supported_transaction_version: Some(0),
+                    },
                 ) {
                     instruction_count += tx.transaction.transaction.message().instructions().len();
+
+                    let (writable, readonly) = self.derive_lock_sets(&tx);
+                    for account in writable {
+                        *write_locks.entry(account).or_insert(0) += 1;
+                    }
+                    for account in readonly {
+                        *read_locks.entry(account).or_insert(0) += 1;
+                    }
+                    txs_considered += 1;
+                }
+            }
+        }
+
+        let account_contention = self.top_contended_accounts(&write_locks, &read_locks);
+
+        if let Some(top) = account_contention.first() {
+            let total_write_locks: usize = write_locks.values().sum();
+            if txs_considered >= 10 && total_write_locks > 0 {
+                let dominance = top.write_locks as f64 / txs_considered as f64;
+                if dominance > 0.5 {
+                    vulnerabilities.push(Vulnerability {
+                        vulnerability_type: "write_lock_contention".to_string(),
+                        severity: Severity::High,
+                        description: format!(
+                            "Account {} is write-locked by {:.0}% of recent transactions, making the program a single-account congestion/DoS target",
+                            top.account, dominance * 100.0
+                        ),
+                        affected_instructions: vec![],
+                        confidence: dominance.min(0.95),
+                    });
                 }
             }
         }
@@ -152,6 +313,7 @@ impl ChainAnalyzer {
             vulnerabilities,
             code_quality,
             recommendations,
+            account_contention,
             metadata: AuditMetadata {
                 timestamp: Utc::now().timestamp(),
                 audit_duration_ms: audit_duration,
@@ -160,6 +322,12 @@ impl ChainAnalyzer {
             },
         };
 
+        if let Some(storage) = &self.storage {
+            if let Err(e) = storage.record_audit(&response).await {
+                warn!("Failed to persist audit for {}: {}", request.program_id, e);
+            }
+        }
+
         info!(
             "Audit complete for {} - Risk Score: {:.2}, Duration: {}ms",
             request.program_id, risk_score, audit_duration
@@ -168,6 +336,109 @@ impl ChainAnalyzer {
         Ok(response)
     }
 
+    pub async fn history_for_program(
+        &self,
+        program_id: &str,
+    ) -> Result<Vec<crate::storage::AnalysisHistoryEntry>> {
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or_else(|| anyhow!("Historical queries require DATABASE_URL to be configured"))?;
+        storage.history_for_program(program_id).await
+    }
+
+    pub async fn high_risk_history(
+        &self,
+        min_score: f64,
+    ) -> Result<Vec<crate::storage::AnalysisHistoryEntry>> {
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or_else(|| anyhow!("Historical queries require DATABASE_URL to be configured"))?;
+        storage.high_risk(min_score).await
+    }
+
+    /// Derives the writable and read-only account sets for a transaction from its
+    /// message header: signer accounts are writable unless covered by
+    /// `num_readonly_signed_accounts`, and non-signer accounts are writable unless
+    /// covered by `num_readonly_unsigned_accounts`. The sysvar/builtin and
+    /// invoked-program demotion that `ExploitDetector` applies is not applied here.
+    /// For v0 messages, loaded-address accounts from the Address Lookup Table are
+    /// included using the writable/readonly split the runtime already resolved.
+    fn derive_lock_sets(
+        &self,
+        transaction: &solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta,
+    ) -> (Vec<String>, Vec<String>) {
+        let Some(decoded) = transaction.transaction.transaction.decode() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let (account_keys, header) = match &decoded.message {
+            VersionedMessage::Legacy(m) => (m.account_keys.clone(), m.header),
+            VersionedMessage::V0(m) => (m.account_keys.clone(), m.header),
+        };
+
+        let num_keys = account_keys.len();
+        let num_required_signatures = header.num_required_signatures as usize;
+        let num_readonly_signed = header.num_readonly_signed_accounts as usize;
+        let num_readonly_unsigned = header.num_readonly_unsigned_accounts as usize;
+
+        let mut writable = Vec::new();
+        let mut readonly = Vec::new();
+
+        for (idx, key) in account_keys.iter().enumerate() {
+            let is_writable = if idx < num_required_signatures {
+                idx < num_required_signatures.saturating_sub(num_readonly_signed)
+            } else {
+                idx < num_keys.saturating_sub(num_readonly_unsigned)
+            };
+
+            if is_writable {
+                writable.push(key.to_string());
+            } else {
+                readonly.push(key.to_string());
+            }
+        }
+
+        if matches!(&decoded.message, VersionedMessage::V0(_)) {
+            if let Some(meta) = transaction.transaction.meta.as_ref() {
+                if let solana_transaction_status::option_serializer::OptionSerializer::Some(loaded) =
+                    &meta.loaded_addresses
+                {
+                    writable.extend(loaded.writable.iter().cloned());
+                    readonly.extend(loaded.readonly.iter().cloned());
+                }
+            }
+        }
+
+        (writable, readonly)
+    }
+
+    /// Ranks accounts by how often they were write-locked across the sampled
+    /// transactions, since a single dominant writable account is the classic
+    /// single-account bottleneck that makes a protocol spammable.
+    fn top_contended_accounts(
+        &self,
+        write_locks: &std::collections::HashMap<String, usize>,
+        read_locks: &std::collections::HashMap<String, usize>,
+    ) -> Vec<AccountLockStat> {
+        let mut accounts: Vec<AccountLockStat> = write_locks
+            .keys()
+            .chain(read_locks.keys())
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .map(|account| AccountLockStat {
+                account: account.clone(),
+                write_locks: *write_locks.get(account).unwrap_or(&0),
+                read_locks: *read_locks.get(account).unwrap_or(&0),
+            })
+            .collect();
+
+        accounts.sort_by(|a, b| b.write_locks.cmp(&a.write_locks));
+        accounts.truncate(10);
+        accounts
+    }
+
     fn analyze_state_changes(&self, transaction: &solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta) -> Result<Vec<StateChange>> {
         let mut changes = Vec::new();
 
@@ -281,6 +552,16 @@ impl ChainAnalyzer {
                         "Use checked arithmetic operations to prevent overflow vulnerabilities".to_string()
                     );
                 }
+                "rounding_direction" => {
+                    recommendations.push(
+                        "Round against the user (try_floor_u64) for value-conservation math instead of try_round_u64".to_string()
+                    );
+                }
+                "unsafe_saturating_arithmetic" => {
+                    recommendations.push(
+                        "Replace saturating_* balance math with checked_* arithmetic and propagate the error".to_string()
+                    );
+                }
                 _ => {}
             }
         }
@@ -0,0 +1,151 @@
+use crate::analyzer::ChainAnalyzer;
+use crate::models::{AnalysisRequest, AnalysisResponse};
+use anyhow::{anyhow, Result};
+use log::{debug, error, info, warn};
+use lru::LruCache;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterTransactions,
+};
+
+/// Continuously watches a set of programs/accounts over a Yellowstone gRPC Geyser
+/// stream and runs them through the existing analyzer pipeline as they confirm,
+/// rather than waiting for a client to POST `/analyze/transaction` on demand.
+pub struct GeyserMonitor {
+    endpoint: String,
+    x_token: Option<String>,
+    account_include: Vec<String>,
+    analyzer: Arc<ChainAnalyzer>,
+    seen_signatures: Mutex<LruCache<String, ()>>,
+    high_risk_threshold: f64,
+}
+
+impl GeyserMonitor {
+    pub fn new(
+        endpoint: String,
+        x_token: Option<String>,
+        account_include: Vec<String>,
+        analyzer: Arc<ChainAnalyzer>,
+    ) -> Self {
+        Self {
+            endpoint,
+            x_token,
+            account_include,
+            analyzer,
+            seen_signatures: Mutex::new(LruCache::new(NonZeroUsize::new(10_000).unwrap())),
+            high_risk_threshold: 70.0,
+        }
+    }
+
+    /// Builds a monitor from env vars, mirroring how `SOLANA_RPC_URL` is read in `main`.
+    /// Returns `None` when `GEYSER_ENDPOINT` is unset, since streaming is optional.
+    pub fn from_env(analyzer: Arc<ChainAnalyzer>) -> Option<Self> {
+        let endpoint = std::env::var("GEYSER_ENDPOINT").ok()?;
+        let x_token = std::env::var("GEYSER_X_TOKEN").ok();
+        let account_include = std::env::var("GEYSER_ACCOUNTS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        Some(Self::new(endpoint, x_token, account_include, analyzer))
+    }
+
+    /// Runs the subscription loop, pushing high-risk analyses onto `high_risk_tx`.
+    /// Runs until the stream ends, then returns `Ok(())`; the caller should
+    /// `tokio::spawn` it.
+    pub async fn run(&self, high_risk_tx: mpsc::Sender<AnalysisResponse>) -> Result<()> {
+        info!("Connecting to Geyser endpoint: {}", self.endpoint);
+
+        let mut client = GeyserGrpcClient::build_from_shared(self.endpoint.clone())?
+            .x_token(self.x_token.clone())?
+            .connect()
+            .await
+            .map_err(|e| anyhow!("Failed to connect to Geyser endpoint: {}", e))?;
+
+        let mut transactions = HashMap::new();
+        transactions.insert(
+            "dynamo".to_string(),
+            SubscribeRequestFilterTransactions {
+                vote: Some(false),
+                failed: Some(false),
+                signature: None,
+                account_include: self.account_include.clone(),
+                account_exclude: vec![],
+                account_required: vec![],
+            },
+        );
+
+        let request = SubscribeRequest {
+            transactions,
+            commitment: Some(CommitmentLevel::Confirmed as i32),
+            ..Default::default()
+        };
+
+        let (mut stream, _) = client.subscribe_with_request(Some(request)).await?;
+
+        use futures::StreamExt;
+        while let Some(update) = stream.next().await {
+            let update = match update {
+                Ok(update) => update,
+                Err(e) => {
+                    warn!("Geyser stream error: {}", e);
+                    continue;
+                }
+            };
+
+            let Some(UpdateOneof::Transaction(tx_update)) = update.update_oneof else {
+                continue;
+            };
+
+            let Some(tx_info) = tx_update.transaction else {
+                continue;
+            };
+
+            let signature = bs58::encode(&tx_info.signature).into_string();
+
+            {
+                let mut seen = self.seen_signatures.lock().unwrap();
+                if seen.put(signature.clone(), ()).is_some() {
+                    debug!("Skipping already-seen signature: {}", signature);
+                    continue;
+                }
+            }
+
+            let analyzer = self.analyzer.clone();
+            let high_risk_tx = high_risk_tx.clone();
+            let threshold = self.high_risk_threshold;
+
+            tokio::spawn(async move {
+                match analyzer
+                    .analyze_transaction(AnalysisRequest {
+                        signature: signature.clone(),
+                        network: None,
+                    })
+                    .await
+                {
+                    Ok(response) => {
+                        if response.risk_score >= threshold {
+                            info!(
+                                "High-risk transaction on stream: {} (score {:.2})",
+                                signature, response.risk_score
+                            );
+                            let _ = high_risk_tx.send(response).await;
+                        }
+                    }
+                    Err(e) => {
+                        error!("Failed to analyze streamed transaction {}: {}", signature, e);
+                    }
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -1,17 +1,22 @@
 mod analyzer;
 mod detector;
+mod geyser;
 mod models;
+mod oracle;
 mod server;
 mod simulator;
+mod storage;
 mod exploit_patterns;
 
 use actix_web::{middleware, App, HttpServer};
 use env_logger::Env;
-use log::info;
+use log::{info, warn};
 use std::sync::Arc;
 
 use crate::analyzer::ChainAnalyzer;
+use crate::geyser::GeyserMonitor;
 use crate::server::configure_routes;
+use crate::storage::Storage;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -24,7 +29,39 @@ async fn main() -> std::io::Result<()> {
     info!("Initializing Dynamo Chain Analyzer");
     info!("Solana RPC: {}", solana_rpc_url);
 
-    let analyzer = Arc::new(ChainAnalyzer::new(&solana_rpc_url));
+    let storage = match std::env::var("DATABASE_URL") {
+        Ok(database_url) => match Storage::connect(&database_url).await {
+            Ok(storage) => Some(Arc::new(storage)),
+            Err(e) => {
+                warn!("Failed to connect to DATABASE_URL, continuing without persistence: {}", e);
+                None
+            }
+        },
+        Err(_) => {
+            info!("DATABASE_URL not set, historical persistence disabled");
+            None
+        }
+    };
+
+    let analyzer = Arc::new(ChainAnalyzer::new(&solana_rpc_url).with_storage(storage));
+
+    if let Some(monitor) = GeyserMonitor::from_env(analyzer.clone()) {
+        let (high_risk_tx, mut high_risk_rx) = tokio::sync::mpsc::channel(100);
+
+        tokio::spawn(async move {
+            while let Some(response) = high_risk_rx.recv().await {
+                warn!("High-risk transaction detected: risk_score={:.2}", response.risk_score);
+            }
+        });
+
+        tokio::spawn(async move {
+            if let Err(e) = monitor.run(high_risk_tx).await {
+                warn!("Geyser monitor stopped: {}", e);
+            }
+        });
+    } else {
+        info!("GEYSER_ENDPOINT not set, streaming monitor disabled");
+    }
 
     info!("Starting HTTP server on {}", bind_address);
 
@@ -14,6 +14,16 @@ pub struct ContractAuditRequest {
     pub depth: Option<AuditDepth>,
 }
 
+/// A transaction that has not landed on chain yet, submitted for a dry-run via
+/// `simulateTransaction` rather than the historical replay `AnalysisRequest` performs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulateTransactionRequest {
+    /// Base64-encoded, wire-format (signed or unsigned) transaction, as produced by
+    /// `Transaction::serialize`/`VersionedTransaction::serialize` + base64 encoding.
+    pub transaction: String,
+    pub network: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AuditDepth {
@@ -73,6 +83,9 @@ pub enum ExploitType {
     DuplicateAccountMutable,
     AccountReinitialization,
     ClosedAccountRevival,
+    MalformedAddressLookup,
+    RoundingDirection,
+    UnsafeSaturatingArithmetic,
     Unknown,
 }
 
@@ -102,6 +115,16 @@ pub struct SimulationResult {
     pub compute_units_consumed: u64,
     pub logs: Vec<String>,
     pub accounts_accessed: Vec<String>,
+    pub cpi_tree: Vec<CpiNode>,
+}
+
+/// A single cross-program invocation, reconstructed from `Program <id> invoke [<depth>]`
+/// / `Program <id> success|failed` log pairs rather than a raw count of "invoke" lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpiNode {
+    pub program_id: String,
+    pub depth: usize,
+    pub children: Vec<CpiNode>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,9 +142,19 @@ pub struct ContractAuditResponse {
     pub vulnerabilities: Vec<Vulnerability>,
     pub code_quality: CodeQuality,
     pub recommendations: Vec<String>,
+    pub account_contention: Vec<AccountLockStat>,
     pub metadata: AuditMetadata,
 }
 
+/// How often an account was write- or read-locked across the sampled transactions
+/// of a program, used to surface write-lock hotspots that make a protocol spammable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountLockStat {
+    pub account: String,
+    pub write_locks: usize,
+    pub read_locks: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vulnerability {
     pub vulnerability_type: String,
@@ -0,0 +1,183 @@
+use crate::models::{Exploit, ExploitType, Severity, SimulationResult};
+use anyhow::Result;
+use log::debug;
+use pyth_sdk_solana::state::load_price_account;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::str::FromStr;
+
+/// Known Pyth price-feed program owners. Kept alongside `SWITCHBOARD_PROGRAM_IDS`
+/// so new oracle providers are a one-line addition rather than a new detector.
+const PYTH_PROGRAM_IDS: &[&str] = &[
+    "FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH",
+    "gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s",
+];
+
+const SWITCHBOARD_PROGRAM_IDS: &[&str] = &[
+    "SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f",
+    "2TfB33aLaneQb79NYZkm8oiXPB5kF2UmrYFwR2Tq3LPR",
+];
+
+/// Grounds `ExploitType::OracleManipulation` in real oracle data:
+/// it recognizes price-feed accounts among a transaction's accessed accounts, fetches
+/// the feed, and checks whether the price the instruction appears to have used falls
+/// outside the feed's reported confidence band.
+pub struct OracleChecker {}
+
+impl OracleChecker {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn check(
+        &self,
+        rpc_client: &RpcClient,
+        transaction: &EncodedConfirmedTransactionWithStatusMeta,
+        simulation: &SimulationResult,
+    ) -> Result<Option<Exploit>> {
+        for account in &simulation.accounts_accessed {
+            let Ok(pubkey) = Pubkey::from_str(account) else {
+                continue;
+            };
+
+            let Ok(owner) = self.account_owner(rpc_client, &pubkey) else {
+                continue;
+            };
+
+            if !PYTH_PROGRAM_IDS.contains(&owner.as_str())
+                && !SWITCHBOARD_PROGRAM_IDS.contains(&owner.as_str())
+            {
+                continue;
+            }
+
+            debug!("Transaction references price feed account {}", account);
+
+            let Ok(price_data) = rpc_client.get_account_data(&pubkey) else {
+                continue;
+            };
+
+            // Only Pyth accounts parse into a price and confidence; a feed that doesn't
+            // (e.g. Switchboard) gets a zero price, which skips the price comparison
+            // below but still runs the balance-change cross-check.
+            let (feed_price, feed_confidence) = match load_price_account(&price_data) {
+                Ok(price_account) => {
+                    let scale = 10f64.powi(price_account.expo);
+                    (price_account.agg.price as f64 * scale, price_account.agg.conf as f64 * scale)
+                }
+                Err(_) => (0.0, 0.0),
+            };
+
+            let anchored_price = Self::parse_used_price_from_logs(&simulation.logs, account);
+            // An anchored log scrape still has no guaranteed scale/unit match to
+            // feed_price (a program logging an unrelated raw fixed-point value, for
+            // instance), so only trust it as a real comparison when it's within two
+            // orders of magnitude of the feed price; otherwise it isn't plausibly the
+            // same quantity and asserting a Critical finding from it is a false positive.
+            let plausible_same_quantity = anchored_price.is_some_and(|used_price| {
+                feed_price.abs() > f64::EPSILON && {
+                    let ratio = (used_price / feed_price).abs();
+                    (0.01..=100.0).contains(&ratio)
+                }
+            });
+
+            if plausible_same_quantity {
+                let used_price = anchored_price.unwrap();
+                let deviation = (used_price - feed_price).abs();
+                if deviation > feed_confidence.max(f64::EPSILON) * 2.0 {
+                    return Ok(Some(Exploit {
+                        exploit_type: ExploitType::OracleManipulation,
+                        severity: Severity::Critical,
+                        description: format!(
+                            "Price used in transaction ({:.6}) deviates from feed {} reported price ({:.6} +/- {:.6}) beyond its confidence band",
+                            used_price, account, feed_price, feed_confidence
+                        ),
+                        location: account.clone(),
+                        confidence: 0.9,
+                        remediation: Some(
+                            "Validate oracle data freshness and confidence interval before using the price".to_string(),
+                        ),
+                    }));
+                }
+            } else if self.has_suspicious_balance_change(transaction) {
+                return Ok(Some(Exploit {
+                    exploit_type: ExploitType::OracleManipulation,
+                    severity: Severity::High,
+                    description: format!(
+                        "Price feed {} was read in a transaction that also produced a large-ratio balance change",
+                        account
+                    ),
+                    location: account.clone(),
+                    confidence: 0.6,
+                    remediation: Some(
+                        "Validate oracle data freshness and use multiple oracle sources".to_string(),
+                    ),
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn account_owner(&self, rpc_client: &RpcClient, pubkey: &Pubkey) -> Result<String> {
+        let account = rpc_client.get_account(pubkey)?;
+        Ok(account.owner.to_string())
+    }
+
+    /// Best-effort extraction of the price an instruction logged using, so it can be
+    /// compared against the feed's reported price. Anchored to log lines that also
+    /// mention the specific price-feed account being cross-referenced — a bare "any
+    /// line containing price" scrape picks up unrelated numbers from unrelated log
+    /// lines and previously produced false-positive Critical findings.
+    fn parse_used_price_from_logs(logs: &[String], account: &str) -> Option<f64> {
+        for log in logs {
+            if !log.contains(account) {
+                continue;
+            }
+
+            // Strip the account pubkey itself before tokenizing: base58 pubkeys contain
+            // digit runs (e.g. "FsJ3A3u2vn5…") that would otherwise parse as the "first
+            // number on the line" ahead of the actual price, as in
+            // "price for FsJ3A3u2vn5…epH = 150.25" (which used to return 3.0).
+            let scrubbed = log.replace(account, " ");
+            let lower = scrubbed.to_lowercase();
+
+            let Some(marker_idx) = lower.find("price") else {
+                continue;
+            };
+
+            // Anchor on a `price[:=]` marker so we grab the number actually assigned to
+            // a price, not an unrelated digit run elsewhere on the line.
+            let Some(value_str) = scrubbed[marker_idx..].split([':', '=']).nth(1) else {
+                continue;
+            };
+
+            for token in value_str.split(|c: char| !c.is_ascii_digit() && c != '.' && c != '-') {
+                if let Ok(value) = token.parse::<f64>() {
+                    if token.contains('.') || value.abs() > 0.0 {
+                        return Some(value);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn has_suspicious_balance_change(&self, transaction: &EncodedConfirmedTransactionWithStatusMeta) -> bool {
+        let Some(meta) = transaction.transaction.meta.as_ref() else {
+            return false;
+        };
+
+        meta.pre_balances
+            .iter()
+            .zip(meta.post_balances.iter())
+            .any(|(pre, post)| {
+                if post > pre {
+                    let ratio = (*post - *pre) as f64 / (*pre).max(1) as f64;
+                    ratio > 10.0
+                } else {
+                    false
+                }
+            })
+    }
+}
@@ -32,6 +32,19 @@ async fn analyze_transaction(
     }
 }
 
+#[post("/simulate/transaction")]
+async fn simulate_transaction(
+    analyzer: web::Data<Arc<ChainAnalyzer>>,
+    request: web::Json<SimulateTransactionRequest>,
+) -> impl Responder {
+    match analyzer.simulate_transaction(request.into_inner()).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
 #[post("/audit/contract")]
 async fn audit_contract(
     analyzer: web::Data<Arc<ChainAnalyzer>>,
@@ -45,9 +58,43 @@ async fn audit_contract(
     }
 }
 
+#[derive(serde::Deserialize)]
+struct HighRiskQuery {
+    min_score: f64,
+}
+
+#[get("/history/program/{program_id}")]
+async fn history_for_program(
+    analyzer: web::Data<Arc<ChainAnalyzer>>,
+    program_id: web::Path<String>,
+) -> impl Responder {
+    match analyzer.history_for_program(&program_id).await {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+#[get("/history/high-risk")]
+async fn high_risk_history(
+    analyzer: web::Data<Arc<ChainAnalyzer>>,
+    query: web::Query<HighRiskQuery>,
+) -> impl Responder {
+    match analyzer.high_risk_history(query.min_score).await {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(health)
         .service(analyze_transaction)
-        .service(audit_contract);
+        .service(simulate_transaction)
+        .service(audit_contract)
+        .service(history_for_program)
+        .service(high_risk_history);
 }
 
@@ -1,7 +1,15 @@
-use crate::models::SimulationResult;
-use anyhow::Result;
+use crate::models::{CpiNode, SimulationResult};
+use anyhow::{anyhow, Result};
+use base64::Engine;
 use log::debug;
-use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::transaction::{TransactionError, VersionedTransaction};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
+    EncodedTransactionWithStatusMeta, TransactionBinaryEncoding, UiTransactionStatusMeta,
+};
 
 pub struct StateSimulator {}
 
@@ -30,6 +38,7 @@ impl StateSimulator {
         };
 
         let accounts_accessed = self.extract_accounts_accessed(transaction);
+        let cpi_tree = Self::build_cpi_tree(&logs);
 
         Ok(SimulationResult {
             success,
@@ -37,19 +46,203 @@ impl StateSimulator {
             compute_units_consumed: compute_units,
             logs,
             accounts_accessed,
+            cpi_tree,
         })
     }
 
+    /// Runs a genuine pre-execution simulation via the RPC `simulateTransaction` method,
+    /// as opposed to `simulate_transaction` above, which only replays an already-landed
+    /// transaction's recorded `meta`. This is what lets callers risk-score a transaction
+    /// before submitting it.
+    ///
+    /// Alongside the `SimulationResult`, this returns a synthetic
+    /// `EncodedConfirmedTransactionWithStatusMeta` wrapping the still-pending transaction
+    /// and its simulated `err`/logs, so the existing detector and oracle-checker APIs
+    /// (written against a landed transaction) can run unmodified against a transaction
+    /// that hasn't landed yet. Fields the simulation genuinely can't know ahead of landing
+    /// — `pre_balances`/`post_balances`, `loaded_addresses` — are left empty rather than
+    /// guessed, so detectors that depend on them are simply inert for this path.
+    pub fn simulate_pending_transaction(
+        &self,
+        rpc_client: &RpcClient,
+        transaction: &VersionedTransaction,
+    ) -> Result<(SimulationResult, EncodedConfirmedTransactionWithStatusMeta)> {
+        debug!("Simulating pending transaction via simulateTransaction");
+
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: None,
+            encoding: None,
+            accounts: None,
+            min_context_slot: None,
+            inner_instructions: false,
+        };
+
+        let response = rpc_client
+            .simulate_transaction_with_config(transaction, config)
+            .map_err(|e| anyhow!("Failed to simulate transaction: {}", e))?;
+
+        let result = response.value;
+        let accounts_accessed = transaction
+            .message
+            .static_account_keys()
+            .iter()
+            .map(|key| key.to_string())
+            .collect();
+        let logs = result.logs.unwrap_or_default();
+        let cpi_tree = Self::build_cpi_tree(&logs);
+
+        let synthetic_transaction =
+            Self::synthesize_confirmed_transaction(transaction, result.err.clone(), logs.clone());
+
+        Ok((
+            SimulationResult {
+                success: result.err.is_none(),
+                error: result.err.map(|e| format!("{:?}", e)),
+                compute_units_consumed: result.units_consumed.unwrap_or(0),
+                logs,
+                accounts_accessed,
+                cpi_tree,
+            },
+            synthetic_transaction,
+        ))
+    }
+
+    /// Wraps a not-yet-landed transaction and its simulated outcome in the same
+    /// `EncodedConfirmedTransactionWithStatusMeta` shape a landed transaction would have,
+    /// so detectors and the oracle checker don't need a second code path for pending
+    /// transactions.
+    fn synthesize_confirmed_transaction(
+        transaction: &VersionedTransaction,
+        err: Option<TransactionError>,
+        logs: Vec<String>,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        let encoded = base64::engine::general_purpose::STANDARD
+            .encode(bincode::serialize(transaction).unwrap_or_default());
+
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 0,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Binary(encoded, TransactionBinaryEncoding::Base64),
+                meta: Some(UiTransactionStatusMeta {
+                    err: err.clone(),
+                    status: err.map_or(Ok(()), Err),
+                    fee: 0,
+                    pre_balances: Vec::new(),
+                    post_balances: Vec::new(),
+                    inner_instructions: OptionSerializer::None,
+                    log_messages: OptionSerializer::Some(logs),
+                    pre_token_balances: OptionSerializer::None,
+                    post_token_balances: OptionSerializer::None,
+                    rewards: OptionSerializer::None,
+                    loaded_addresses: OptionSerializer::None,
+                    return_data: OptionSerializer::None,
+                    compute_units_consumed: OptionSerializer::None,
+                }),
+                version: None,
+            },
+            block_time: None,
+        }
+    }
+
+    /// Reconstructs the cross-program-invocation tree from runtime logs. Solana emits
+    /// `Program <pubkey> invoke [<depth>]` on entry and `Program <pubkey> success/failed`
+    /// on exit; this builds a stack-based tree from those pairs so detectors can reason
+    /// about actual call structure instead of a flat count of "invoke" lines.
+    fn build_cpi_tree(logs: &[String]) -> Vec<CpiNode> {
+        let mut roots: Vec<CpiNode> = Vec::new();
+        let mut stack: Vec<CpiNode> = Vec::new();
+
+        for log in logs {
+            if let Some((program_id, depth)) = Self::parse_invoke_log(log) {
+                stack.push(CpiNode {
+                    program_id,
+                    depth,
+                    children: Vec::new(),
+                });
+            } else if Self::is_exit_log(log) {
+                if let Some(node) = stack.pop() {
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(node),
+                        None => roots.push(node),
+                    }
+                }
+            }
+        }
+
+        // Close out any unterminated invocations (e.g. a truncated log buffer) so
+        // partial trees are still returned rather than silently dropped.
+        while let Some(node) = stack.pop() {
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => roots.push(node),
+            }
+        }
+
+        roots
+    }
+
+    fn parse_invoke_log(log: &str) -> Option<(String, usize)> {
+        let rest = log.strip_prefix("Program ")?;
+        let (program_id, rest) = rest.split_once(" invoke [")?;
+        let depth_str = rest.strip_suffix(']')?;
+        let depth = depth_str.parse::<usize>().ok()?;
+        Some((program_id.to_string(), depth))
+    }
+
+    fn is_exit_log(log: &str) -> bool {
+        // `Program log:`/`Program data:` lines carry program output that may itself
+        // contain "success" or "failed"; only `Program <pubkey> success|failed...` exits.
+        let Some((program_id, outcome)) = log
+            .strip_prefix("Program ")
+            .and_then(|rest| rest.split_once(' '))
+        else {
+            return false;
+        };
+        !program_id.ends_with(':') && (outcome == "success" || outcome.starts_with("failed"))
+    }
+
+    /// Decodes a base64-encoded wire-format transaction as submitted by a client that
+    /// wants a risk score before broadcasting it.
+    pub fn decode_transaction(&self, encoded: &str) -> Result<VersionedTransaction> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| anyhow!("Invalid base64 transaction: {}", e))?;
+
+        bincode::deserialize(&bytes).map_err(|e| anyhow!("Invalid transaction encoding: {}", e))
+    }
+
+    /// Builds the full set of accounts touched by the transaction, resolving
+    /// Address Lookup Table entries for v0 messages. Order matches the
+    /// runtime's own `AccountKeys` layout: static keys, then writable loaded
+    /// addresses, then readonly loaded addresses, so callers that index this
+    /// list against `pre_balances`/`post_balances` stay aligned.
     fn extract_accounts_accessed(
         &self,
         transaction: &EncodedConfirmedTransactionWithStatusMeta,
     ) -> Vec<String> {
         let mut accounts = Vec::new();
 
-        // Extract account keys from transaction
         if let Some(tx) = transaction.transaction.transaction.decode() {
-            for key in tx.message.account_keys {
-                accounts.push(key.to_string());
+            match &tx.message {
+                VersionedMessage::Legacy(message) => {
+                    for key in &message.account_keys {
+                        accounts.push(key.to_string());
+                    }
+                }
+                VersionedMessage::V0(message) => {
+                    for key in &message.account_keys {
+                        accounts.push(key.to_string());
+                    }
+
+                    if let Some(meta) = transaction.transaction.meta.as_ref() {
+                        if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+                            accounts.extend(loaded.writable.iter().cloned());
+                            accounts.extend(loaded.readonly.iter().cloned());
+                        }
+                    }
+                }
             }
         }
 
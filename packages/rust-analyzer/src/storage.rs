@@ -0,0 +1,215 @@
+use crate::models::{AnalysisResponse, ContractAuditResponse};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use log::info;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{PgPool, Row};
+
+/// Persists analysis/audit results so they can be queried by program or by minimum
+/// risk score (newest or riskiest first), instead of only living in the in-memory
+/// `LruCache` until restart. There is no query by time window.
+pub struct Storage {
+    pool: PgPool,
+}
+
+/// A single historical analysis row, as returned by the `/history/*` routes.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AnalysisHistoryEntry {
+    pub signature: String,
+    pub risk_score: f64,
+    pub exploits: serde_json::Value,
+    pub state_changes: serde_json::Value,
+    pub compute_units_consumed: i64,
+    pub network: String,
+    pub analyzed_at: DateTime<Utc>,
+}
+
+impl Storage {
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        info!("Connecting to analysis history database");
+
+        let pool = PgPoolOptions::new()
+            .max_connections(10)
+            .connect(database_url)
+            .await
+            .map_err(|e| anyhow!("Failed to connect to database: {}", e))?;
+
+        let storage = Self { pool };
+        storage.run_migrations().await?;
+        Ok(storage)
+    }
+
+    async fn run_migrations(&self) -> Result<()> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS analyses (
+                signature TEXT PRIMARY KEY,
+                risk_score DOUBLE PRECISION NOT NULL,
+                exploits JSONB NOT NULL,
+                state_changes JSONB NOT NULL,
+                compute_units_consumed BIGINT NOT NULL,
+                network TEXT NOT NULL,
+                analyzed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_analyses_risk_score ON analyses (risk_score)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS analysis_programs (
+                signature TEXT NOT NULL REFERENCES analyses(signature) ON DELETE CASCADE,
+                program_id TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_analysis_programs_program_id ON analysis_programs (program_id)",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS audits (
+                id BIGSERIAL PRIMARY KEY,
+                program_id TEXT NOT NULL,
+                risk_score DOUBLE PRECISION NOT NULL,
+                vulnerabilities JSONB NOT NULL,
+                recommendations JSONB NOT NULL,
+                audited_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_audits_program_id ON audits (program_id)")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn record_analysis(
+        &self,
+        signature: &str,
+        programs_accessed: &[String],
+        response: &AnalysisResponse,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO analyses (signature, risk_score, exploits, state_changes, compute_units_consumed, network)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (signature) DO UPDATE SET
+                risk_score = EXCLUDED.risk_score,
+                exploits = EXCLUDED.exploits,
+                state_changes = EXCLUDED.state_changes,
+                compute_units_consumed = EXCLUDED.compute_units_consumed,
+                network = EXCLUDED.network,
+                analyzed_at = now()
+            "#,
+        )
+        .bind(signature)
+        .bind(response.risk_score)
+        .bind(serde_json::to_value(&response.exploits)?)
+        .bind(serde_json::to_value(&response.state_changes)?)
+        .bind(response.simulation_result.compute_units_consumed as i64)
+        .bind(&response.metadata.network)
+        .execute(&self.pool)
+        .await?;
+
+        // `analyses` is upserted above, but re-analyzing the same signature would
+        // otherwise append a second copy of every program row on top of the first,
+        // compounding with each re-analysis. Replace the program rows for this
+        // signature outright rather than inserting unconditionally.
+        sqlx::query("DELETE FROM analysis_programs WHERE signature = $1")
+            .bind(signature)
+            .execute(&self.pool)
+            .await?;
+
+        for program_id in programs_accessed {
+            sqlx::query("INSERT INTO analysis_programs (signature, program_id) VALUES ($1, $2)")
+                .bind(signature)
+                .bind(program_id)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn record_audit(&self, response: &ContractAuditResponse) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO audits (program_id, risk_score, vulnerabilities, recommendations)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(&response.program_id)
+        .bind(response.risk_score)
+        .bind(serde_json::to_value(&response.vulnerabilities)?)
+        .bind(serde_json::to_value(&response.recommendations)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn history_for_program(&self, program_id: &str) -> Result<Vec<AnalysisHistoryEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.signature, a.risk_score, a.exploits, a.state_changes,
+                   a.compute_units_consumed, a.network, a.analyzed_at
+            FROM analyses a
+            JOIN analysis_programs p ON p.signature = a.signature
+            WHERE p.program_id = $1
+            ORDER BY a.analyzed_at DESC
+            LIMIT 200
+            "#,
+        )
+        .bind(program_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_entry).collect()
+    }
+
+    pub async fn high_risk(&self, min_score: f64) -> Result<Vec<AnalysisHistoryEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT signature, risk_score, exploits, state_changes,
+                   compute_units_consumed, network, analyzed_at
+            FROM analyses
+            WHERE risk_score >= $1
+            ORDER BY risk_score DESC, analyzed_at DESC
+            LIMIT 200
+            "#,
+        )
+        .bind(min_score)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_entry).collect()
+    }
+
+    fn row_to_entry(row: sqlx::postgres::PgRow) -> Result<AnalysisHistoryEntry> {
+        Ok(AnalysisHistoryEntry {
+            signature: row.try_get("signature")?,
+            risk_score: row.try_get("risk_score")?,
+            exploits: row.try_get("exploits")?,
+            state_changes: row.try_get("state_changes")?,
+            compute_units_consumed: row.try_get("compute_units_consumed")?,
+            network: row.try_get("network")?,
+            analyzed_at: row.try_get("analyzed_at")?,
+        })
+    }
+}